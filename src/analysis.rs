@@ -3,11 +3,12 @@
 // centrality metrics, clustering, and genre trends.
 
 use petgraph::graph::NodeIndex;
-use petgraph::visit::{Bfs};
-use petgraph::algo::{dijkstra};
+use petgraph::visit::{Bfs, EdgeRef};
+use petgraph::unionfind::UnionFind;
 use petgraph::Graph;
 use crate::graph_builder::MovieNode;
-use std::collections::{HashMap, HashSet};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use petgraph::Undirected;
 
 // This will compute degree centrality for all nodes in the graph.
@@ -25,24 +26,201 @@ pub fn degree_centrality(graph: &Graph<MovieNode, f64, Undirected>) -> HashMap<S
     centrality
 }
 
-// Computing betweenness centrality by counting the amount
-// of times a node appears in the shortest paths
+// A min-heap entry for the Dijkstra variant of Brandes' algorithm below.
+// Ordering is reversed so that BinaryHeap (a max-heap) pops the closest node first.
+struct HeapState {
+    dist: f64,
+    node: NodeIndex,
+}
+
+impl PartialEq for HeapState {
+    fn eq(&self, other: &Self) -> bool {
+        self.dist == other.dist
+    }
+}
+impl Eq for HeapState {}
+impl Ord for HeapState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.dist.partial_cmp(&self.dist).unwrap_or(Ordering::Equal)
+    }
+}
+impl PartialOrd for HeapState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The per-source bookkeeping Brandes' algorithm needs before it can
+// accumulate dependencies: the nodes in the order they were finalized
+// (farthest last), each node's predecessors on a shortest path from src,
+// and the number of shortest paths sigma reaching each node.
+type BrandesState = (Vec<NodeIndex>, HashMap<NodeIndex, Vec<NodeIndex>>, HashMap<NodeIndex, f64>);
+
+// Edge weights in this graph are similarity scores (higher = more related),
+// but Dijkstra needs a distance (lower = closer), so a raw weight fed
+// straight into Dijkstra would route "shortest" paths through the weakest
+// connections instead of the strongest. Inverting turns strong similarity
+// into a short hop, so the shortest path actually follows the strongest
+// connections. Floored away from zero so a near-zero similarity can't
+// produce an infinite or near-infinite distance.
+const MIN_SIMILARITY_FOR_DISTANCE: f64 = 1e-6;
+
+fn similarity_to_distance(weight: f64) -> f64 {
+    1.0 / weight.max(MIN_SIMILARITY_FOR_DISTANCE)
+}
+
+// Single-source shortest paths via Dijkstra with a binary heap. Edge
+// weights (similarity strength) are inverted into distances so stronger
+// connections pull more weight onto the shortest-path computation.
+// Ties within a small epsilon are treated as equal-length so sigma counts
+// every shortest path, not just the first one found.
+fn brandes_dijkstra(graph: &Graph<MovieNode, f64, Undirected>, src: NodeIndex) -> BrandesState {
+    const EPS: f64 = 1e-9;
+    let mut dist: HashMap<NodeIndex, f64> = HashMap::new();
+    let mut sigma: HashMap<NodeIndex, f64> = HashMap::new();
+    let mut preds: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+    let mut stack = Vec::new();
+    let mut finalized: HashSet<NodeIndex> = HashSet::new();
+    let mut heap = BinaryHeap::new();
+
+    dist.insert(src, 0.0);
+    sigma.insert(src, 1.0);
+    heap.push(HeapState { dist: 0.0, node: src });
+
+    while let Some(HeapState { dist: d, node: v }) = heap.pop() {
+        if !finalized.insert(v) {
+            continue; // already finalized with a shorter or equal distance
+        }
+        stack.push(v);
+
+        for edge in graph.edges(v) {
+            let w = edge.target();
+            let alt = d + similarity_to_distance(*edge.weight());
+            match dist.get(&w).copied() {
+                None => {
+                    dist.insert(w, alt);
+                    sigma.insert(w, sigma[&v]);
+                    preds.insert(w, vec![v]);
+                    heap.push(HeapState { dist: alt, node: w });
+                }
+                Some(existing) if alt < existing - EPS => {
+                    dist.insert(w, alt);
+                    sigma.insert(w, sigma[&v]);
+                    preds.insert(w, vec![v]);
+                    heap.push(HeapState { dist: alt, node: w });
+                }
+                Some(existing) if (alt - existing).abs() <= EPS => {
+                    *sigma.entry(w).or_insert(0.0) += sigma[&v];
+                    preds.entry(w).or_default().push(v);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    (stack, preds, sigma)
+}
+
+// Single-source shortest paths via unit-weight BFS, the fast mode for when
+// edge strengths don't matter and only hop count does.
+fn brandes_bfs(graph: &Graph<MovieNode, f64, Undirected>, src: NodeIndex) -> BrandesState {
+    let mut dist: HashMap<NodeIndex, u32> = HashMap::new();
+    let mut sigma: HashMap<NodeIndex, f64> = HashMap::new();
+    let mut preds: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+    let mut stack = Vec::new();
+    let mut queue = VecDeque::new();
+
+    dist.insert(src, 0);
+    sigma.insert(src, 1.0);
+    queue.push_back(src);
+
+    while let Some(v) = queue.pop_front() {
+        stack.push(v);
+        let dv = dist[&v];
+
+        for edge in graph.edges(v) {
+            let w = edge.target();
+            match dist.get(&w).copied() {
+                None => {
+                    dist.insert(w, dv + 1);
+                    sigma.insert(w, sigma[&v]);
+                    preds.insert(w, vec![v]);
+                    queue.push_back(w);
+                }
+                Some(dw) if dw == dv + 1 => {
+                    *sigma.entry(w).or_insert(0.0) += sigma[&v];
+                    preds.entry(w).or_default().push(v);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    (stack, preds, sigma)
+}
+
+// Brandes' dependency accumulation: pop the finalized stack in reverse
+// (farthest first) so every node's dependents have already contributed
+// their delta by the time we process it, then credit delta[v] to v unless
+// v is the source itself.
+fn accumulate_dependencies(
+    graph: &Graph<MovieNode, f64, Undirected>,
+    src: NodeIndex,
+    stack: Vec<NodeIndex>,
+    preds: HashMap<NodeIndex, Vec<NodeIndex>>,
+    sigma: HashMap<NodeIndex, f64>,
+    centrality: &mut HashMap<String, f64>,
+) {
+    let mut delta: HashMap<NodeIndex, f64> = HashMap::new();
+
+    for &v in stack.iter().rev() {
+        let coeff = (1.0 + *delta.get(&v).unwrap_or(&0.0)) / sigma[&v];
+        if let Some(ps) = preds.get(&v) {
+            for &w in ps {
+                *delta.entry(w).or_insert(0.0) += sigma[&w] * coeff;
+            }
+        }
+        if v != src {
+            let movie = &graph[v];
+            *centrality.entry(movie.title.clone()).or_insert(0.0) += *delta.get(&v).unwrap_or(&0.0);
+        }
+    }
+}
+
+// Computing true betweenness centrality via Brandes' algorithm, using edge
+// weights (similarity strength) so stronger connections pull more weight
+// onto the shortest-path computation.
 // The argument is an undirected graph
 // This will return a HashMap of the keys being the movie title
 // and the value is a float that represents centrality
-// To do this, for every node that has a source, it will run Dijstra's algorithm
-// Then it will count how often each destination node appears in paths from others
+// To do this, for every source node it runs a weighted Dijkstra that tracks
+// sigma (shortest path counts) and predecessors, pushes nodes onto a stack
+// as they're finalized, then pops the stack in reverse to accumulate each
+// node's dependency. Since the graph is undirected every pair is counted
+// twice (once from each endpoint as source), so the final scores are halved.
 pub fn betweenness_centrality(graph: &Graph<MovieNode, f64, Undirected>) -> HashMap<String, f64> {
     let mut centrality = HashMap::new();
-    let nodes: Vec<NodeIndex> = graph.node_indices().collect();
-    for &src in &nodes {
-        let paths = dijkstra(graph, src, None, |_| 1.0);
-        for (&dst, &dist) in &paths {
-            if src != dst && dist > 0.0 {
-                let movie = &graph[dst];
-                *centrality.entry(movie.title.clone()).or_insert(0.0) += 1.0;
-            }
-        }
+    for src in graph.node_indices() {
+        let (stack, preds, sigma) = brandes_dijkstra(graph, src);
+        accumulate_dependencies(graph, src, stack, preds, sigma, &mut centrality);
+    }
+    for value in centrality.values_mut() {
+        *value /= 2.0;
+    }
+    centrality
+}
+
+// Same as betweenness_centrality but ignores edge weights and uses
+// unit-weight BFS shortest paths instead of weighted Dijkstra, a faster
+// mode for when only hop count (not similarity strength) matters.
+pub fn betweenness_centrality_fast(graph: &Graph<MovieNode, f64, Undirected>) -> HashMap<String, f64> {
+    let mut centrality = HashMap::new();
+    for src in graph.node_indices() {
+        let (stack, preds, sigma) = brandes_bfs(graph, src);
+        accumulate_dependencies(graph, src, stack, preds, sigma, &mut centrality);
+    }
+    for value in centrality.values_mut() {
+        *value /= 2.0;
     }
     centrality
 }
@@ -88,13 +266,16 @@ pub fn average_score_gap_by_genre(movies: &[crate::data_cleaning::CleanMovie]) -
         .collect()
 }
 
-// Clustering movies by connected components in the graph.
+// Clustering movies by connected components in the graph, keyed by node
+// index rather than title, since movie titles aren't unique (IMDb has
+// remakes and re-releases) and a title-keyed result can't tell two
+// same-titled movies in different clusters apart.
 // The argument is a undirected graph
-// This will return a vector of clusters that will be a list of movie titles
-// To do so, it will use Breadh-First Search in order to explore the nodes
+// This will return a vector of clusters that will be a list of node indices
+// To do so, it will use Breadth-First Search in order to explore the nodes
 // Start a new cluster for each unvisited node
-// THen the reachable nodes are added to the cluster
-pub fn find_clusters(graph: &Graph<MovieNode, f64, Undirected>) -> Vec<Vec<String>> {
+// Then the reachable nodes are added to the cluster
+pub fn find_clusters_by_node(graph: &Graph<MovieNode, f64, Undirected>) -> Vec<Vec<NodeIndex>> {
     let mut visited = HashSet::new(); // tracking the nodes already seen
     let mut clusters = Vec::new(); // final list of the clusterings
 
@@ -107,7 +288,7 @@ pub fn find_clusters(graph: &Graph<MovieNode, f64, Undirected>) -> Vec<Vec<Strin
         let mut bfs = Bfs::new(graph, node);
         while let Some(nx) = bfs.next(graph) { // exploring the connected components
             if visited.insert(nx) {
-                cluster.push(graph[nx].title.clone());
+                cluster.push(nx);
             }
         }
         clusters.push(cluster); // adding the component to the result
@@ -116,12 +297,78 @@ pub fn find_clusters(graph: &Graph<MovieNode, f64, Undirected>) -> Vec<Vec<Strin
     clusters
 }
 
+// Clustering movies by connected components in the graph.
+// The argument is a undirected graph
+// This will return a vector of clusters that will be a list of movie titles
+// To do so, it delegates to find_clusters_by_node and maps each node to its title.
+pub fn find_clusters(graph: &Graph<MovieNode, f64, Undirected>) -> Vec<Vec<String>> {
+    find_clusters_by_node(graph)
+        .into_iter()
+        .map(|cluster| cluster.into_iter().map(|node| graph[node].title.clone()).collect())
+        .collect()
+}
+
+// Clustering movies with union-find, agglomerating edges in order of
+// descending weight. This is distinct from find_clusters: a low
+// similarity_threshold on the graph can collapse everything into one
+// component, whereas cluster_cutoff lets callers tighten the clustering
+// after the fact without rebuilding the graph.
+// The arguments are a graph and cluster_cutoff, the weight an edge must
+// strictly exceed to union its two nodes into the same cluster.
+// This will return a vector of clusters, each a list of movie titles.
+// To do so, it sorts every edge by descending weight and unions the
+// endpoints of each edge only while its weight exceeds cluster_cutoff,
+// then groups nodes by their root in the resulting disjoint sets.
+pub fn find_clusters_threshold(graph: &Graph<MovieNode, f64, Undirected>, cluster_cutoff: f64) -> Vec<Vec<String>> {
+    let mut uf = UnionFind::new(graph.node_count());
+
+    let mut edges: Vec<(NodeIndex, NodeIndex, f64)> = graph
+        .edge_references()
+        .map(|e| (e.source(), e.target(), *e.weight()))
+        .collect();
+    edges.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(Ordering::Equal));
+
+    for (u, v, weight) in edges {
+        if weight > cluster_cutoff {
+            uf.union(u.index(), v.index());
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<String>> = HashMap::new();
+    for node in graph.node_indices() {
+        let root = uf.find(node.index());
+        groups.entry(root).or_default().push(graph[node].title.clone());
+    }
+
+    groups.into_values().collect()
+}
+
 //Testing to validate the average score gap by the decade
 #[cfg(test)]
 mod tests {
     use super::*;
     use petgraph::Graph;
 
+    // a minimal MovieNode for tests that only care about graph shape, not movie data
+    fn movie(title: &str) -> MovieNode {
+        use crate::data_cleaning::CleanMovie;
+        let clean = CleanMovie {
+            title: title.into(),
+            year: 2000,
+            genres: vec!["Drama".into()],
+            critic_score: 5.0,
+            user_score: 5.0,
+            score_gap: 0.0,
+        };
+        MovieNode {
+            title: clean.title,
+            year: clean.year,
+            genres: clean.genres,
+            critic_score: clean.critic_score,
+            user_score: clean.user_score,
+        }
+    }
+
     #[test]
     fn test_average_score_gap_by_decade() {
         use crate::data_cleaning::CleanMovie;
@@ -146,4 +393,77 @@ mod tests {
         let result = average_score_gap_by_decade(&sample); //expecting average gap for 1990s to be (0.7 + 1.0) / 2 = 0.85
         assert_eq!(result.get(&1990), Some(&0.85));
     }
-} 
+
+    // building a plain path graph A-B-C-D-E with unit-weight edges so the
+    // correct betweenness values are known ahead of time: for a path of n
+    // nodes, node i (1-indexed) has betweenness (i-1)*(n-i)
+    #[test]
+    fn test_betweenness_centrality_on_path_graph() {
+        let mut graph = Graph::<MovieNode, f64, Undirected>::new_undirected();
+        let a = graph.add_node(movie("A"));
+        let b = graph.add_node(movie("B"));
+        let c = graph.add_node(movie("C"));
+        let d = graph.add_node(movie("D"));
+        let e = graph.add_node(movie("E"));
+        graph.add_edge(a, b, 1.0);
+        graph.add_edge(b, c, 1.0);
+        graph.add_edge(c, d, 1.0);
+        graph.add_edge(d, e, 1.0);
+
+        let result = betweenness_centrality_fast(&graph);
+        assert_eq!(result.get("A").copied().unwrap_or(0.0), 0.0);
+        assert_eq!(result.get("B").copied().unwrap_or(0.0), 3.0);
+        assert_eq!(result.get("C").copied().unwrap_or(0.0), 4.0);
+        assert_eq!(result.get("D").copied().unwrap_or(0.0), 3.0);
+        assert_eq!(result.get("E").copied().unwrap_or(0.0), 0.0);
+
+        // weighted mode should agree with the fast mode when every edge has the same weight
+        let weighted = betweenness_centrality(&graph);
+        assert_eq!(weighted.get("C"), result.get("C"));
+    }
+
+    // A is bridged to D via two routes: a direct weak A-D edge, and a
+    // longer detour through B and C made of much stronger edges. Since
+    // stronger similarity must mean a *shorter* distance, the weighted
+    // shortest path should take the strong detour over the weak direct
+    // edge, crediting B and C (not just "the path with fewest hops").
+    // Every edge here has the same weight in test_betweenness_centrality_on_path_graph,
+    // so that test alone can't catch a similarity/distance inversion bug.
+    #[test]
+    fn test_betweenness_centrality_prefers_stronger_connections() {
+        let mut graph = Graph::<MovieNode, f64, Undirected>::new_undirected();
+        let a = graph.add_node(movie("A"));
+        let b = graph.add_node(movie("B"));
+        let c = graph.add_node(movie("C"));
+        let d = graph.add_node(movie("D"));
+        graph.add_edge(a, d, 0.1); // weak direct edge: long distance
+        graph.add_edge(a, b, 0.9); // strong detour: short distance
+        graph.add_edge(b, c, 0.9);
+        graph.add_edge(c, d, 0.9);
+
+        let result = betweenness_centrality(&graph);
+        assert!(result.get("B").copied().unwrap_or(0.0) > 0.0);
+        assert!(result.get("C").copied().unwrap_or(0.0) > 0.0);
+    }
+
+    #[test]
+    fn test_find_clusters_threshold_splits_weak_edges() {
+        // A-B strongly linked, B-C weakly linked: at a low graph threshold
+        // find_clusters would merge all three into one component, but a
+        // tighter cluster_cutoff should split C into its own cluster.
+        let mut graph = Graph::<MovieNode, f64, Undirected>::new_undirected();
+        let a = graph.add_node(movie("A"));
+        let b = graph.add_node(movie("B"));
+        let c = graph.add_node(movie("C"));
+        graph.add_edge(a, b, 0.9);
+        graph.add_edge(b, c, 0.2);
+
+        let whole_component = find_clusters(&graph);
+        assert_eq!(whole_component.len(), 1);
+
+        let tight = find_clusters_threshold(&graph, 0.5);
+        let mut sizes: Vec<usize> = tight.iter().map(|c| c.len()).collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![1, 2]);
+    }
+}
@@ -5,6 +5,8 @@
 use petgraph::graph::{Graph};
 use petgraph::Undirected;
 use crate::data_cleaning::CleanMovie;
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
 
 // A struct for representing a node in the graph.
 // This contains only the key info needed for the camparison and labeling
@@ -89,6 +91,75 @@ pub fn build_graph(movies: &[CleanMovie], genre_weight: f64, score_weight: f64,
     graph
 }
 
+// Same as build_graph, but splits the O(n^2) pairwise similarity computation
+// across num_cores worker threads with rayon instead of running single-threaded.
+// The arguments are the same as build_graph, plus num_cores which is how many
+// worker threads to split the pair space across.
+// This will return the same graph of movie nodes with weighted edges as build_graph.
+// To do so, it will add all movies as nodes up front (sequential, cheap), then
+// give each worker a slice of the `i` indices and have it compute a
+// Vec<(usize, usize, f64)> of the qualifying edges for its slice. Once every
+// worker is done, the main thread drains the collected edge lists and calls
+// graph.add_edge sequentially, since petgraph's Graph isn't Sync for mutation.
+pub fn build_graph_with_cores(
+    movies: &[CleanMovie],
+    genre_weight: f64,
+    score_weight: f64,
+    similarity_threshold: f64,
+    num_cores: usize,
+) -> Graph<MovieNode, f64, Undirected> {
+    let mut graph = Graph::<MovieNode, f64, Undirected>::new_undirected();
+    let mut indices = Vec::new();
+
+    // adding all movies as nodes
+    for movie in movies {
+        let node = MovieNode {
+            title: movie.title.clone(),
+            year: movie.year,
+            genres: movie.genres.clone(),
+            critic_score: movie.critic_score,
+            user_score: movie.user_score,
+        };
+        indices.push(graph.add_node(node));
+    }
+
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(num_cores.max(1))
+        .build()
+        .expect("failed to build rayon thread pool");
+
+    // each worker computes the qualifying edges for its chunk of i indices
+    let edge_lists: Vec<Vec<(usize, usize, f64)>> = pool.install(|| {
+        (0..movies.len())
+            .into_par_iter()
+            .map(|i| {
+                let mut edges = Vec::new();
+                for j in (i + 1)..movies.len() {
+                    let genre_overlap = genre_jaccard(&movies[i].genres, &movies[j].genres);
+                    let score_sim = cosine_similarity(
+                        (movies[i].critic_score, movies[i].user_score),
+                        (movies[j].critic_score, movies[j].user_score),
+                    );
+
+                    let total_similarity = genre_weight * genre_overlap + score_weight * score_sim;
+
+                    if total_similarity >= similarity_threshold {
+                        edges.push((i, j, total_similarity));
+                    }
+                }
+                edges
+            })
+            .collect()
+    });
+
+    // draining the collected edge lists sequentially to populate the graph
+    for (i, j, weight) in edge_lists.into_iter().flatten() {
+        graph.add_edge(indices[i], indices[j], weight);
+    }
+
+    graph
+}
+
 // Computing Jaccard similarity between two genre vectors.
 // Arguments will be g1 and g2 which are from the genre strings
 // This will return f64 between 0 and 1 to represent the overlap
@@ -126,4 +197,19 @@ mod tests {
         let sim = genre_jaccard(&g1, &g2);
         assert_eq!(sim, 1.0 / 3.0); // wanting one shared genre out of the three total
     }
-} 
+
+    #[test]
+    fn test_build_graph_with_cores_matches_single_threaded() {
+        use crate::data_cleaning::CleanMovie;
+        let movies = vec![
+            CleanMovie { title: "A".into(), year: 1994, genres: vec!["Drama".into()], critic_score: 7.5, user_score: 8.2, score_gap: 0.7 },
+            CleanMovie { title: "B".into(), year: 1995, genres: vec!["Drama".into()], critic_score: 7.2, user_score: 8.0, score_gap: 0.8 },
+            CleanMovie { title: "C".into(), year: 1999, genres: vec!["Action".into()], critic_score: 5.0, user_score: 4.0, score_gap: -1.0 },
+        ];
+        let single = build_graph(&movies, 0.5, 0.5, 0.3);
+        let parallel = build_graph_with_cores(&movies, 0.5, 0.5, 0.3, 4);
+        // splitting the work across threads shouldn't change which edges qualify
+        assert_eq!(single.node_count(), parallel.node_count());
+        assert_eq!(single.edge_count(), parallel.edge_count());
+    }
+}
@@ -0,0 +1,222 @@
+// Graph Export
+// Purpose: I want to serialize the similarity graph to formats external
+// tools can load, since the pipeline otherwise only emits CSV tables and a
+// flat cluster text file and there's no way to actually see the movie network.
+
+use crate::graph_builder::MovieNode;
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::visit::{EdgeRef, IntoNodeReferences};
+use petgraph::Undirected;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::Write;
+
+// Building a node -> cluster id lookup from a find_clusters_by_node-style
+// result, so write_dot can color nodes by which cluster they belong to.
+// Keyed by NodeIndex rather than movie title, since titles aren't unique
+// (IMDb has remakes and re-releases) and a title-keyed lookup would let two
+// distinct nodes with the same title silently collide on the same color.
+// The argument is clusters, a vector of clusters each a list of node indices.
+// This will return a HashMap from node index to the index of its cluster.
+pub fn cluster_id_lookup(clusters: &[Vec<NodeIndex>]) -> HashMap<NodeIndex, usize> {
+    let mut lookup = HashMap::new();
+    for (id, cluster) in clusters.iter().enumerate() {
+        for &node in cluster {
+            lookup.insert(node, id);
+        }
+    }
+    lookup
+}
+
+// Escaping a string for use inside a DOT double-quoted attribute value.
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// Escaping a string for use inside GraphML XML text content.
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Writing the similarity graph out to GraphViz DOT format.
+// The arguments are path, the output file path; graph, the similarity graph;
+// and cluster_of, an optional node -> cluster id lookup (from cluster_id_lookup)
+// used to fill each node with a color picked by its cluster id and to tag it
+// with a cluster attribute, with nodes missing from the lookup left uncolored.
+// This will write one node per movie (labeled with title and year, plus year
+// and genres attributes) and one edge per similarity edge with its weight,
+// something loadable directly in Gephi or Cytoscape.
+pub fn write_dot(
+    path: &str,
+    graph: &Graph<MovieNode, f64, Undirected>,
+    cluster_of: &HashMap<NodeIndex, usize>,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(path)?;
+    writeln!(file, "graph MovieGraph {{")?;
+
+    for (node, movie) in graph.node_references() {
+        let label = format!("{} ({})", movie.title, movie.year);
+        write!(
+            file,
+            "  {} [label=\"{}\", year={}, genres=\"{}\"",
+            node.index(),
+            escape_dot(&label),
+            movie.year,
+            escape_dot(&movie.genres.join(";"))
+        )?;
+        if let Some(cluster) = cluster_of.get(&node) {
+            // set312 is a 12-color Brewer scheme numbered 1..=12, so colors repeat every 12 clusters
+            write!(
+                file,
+                ", cluster={}, style=filled, colorscheme=set312, fillcolor={}",
+                cluster,
+                cluster % 12 + 1
+            )?;
+        }
+        writeln!(file, "];")?;
+    }
+
+    for edge in graph.edge_references() {
+        writeln!(
+            file,
+            "  {} -- {} [weight={}];",
+            edge.source().index(),
+            edge.target().index(),
+            edge.weight()
+        )?;
+    }
+
+    writeln!(file, "}}")?;
+    Ok(())
+}
+
+// Writing the similarity graph out to GraphML format.
+// The arguments are path, the output file path, and graph, the similarity graph.
+// This will write one node per movie with title/year/genres attributes and
+// one edge per similarity edge with its weight attribute, loadable in
+// Gephi or Cytoscape alongside the DOT export.
+pub fn write_graphml(path: &str, graph: &Graph<MovieNode, f64, Undirected>) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(path)?;
+
+    writeln!(file, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(file, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    writeln!(file, r#"  <key id="title" for="node" attr.name="title" attr.type="string"/>"#)?;
+    writeln!(file, r#"  <key id="year" for="node" attr.name="year" attr.type="int"/>"#)?;
+    writeln!(file, r#"  <key id="genres" for="node" attr.name="genres" attr.type="string"/>"#)?;
+    writeln!(file, r#"  <key id="weight" for="edge" attr.name="weight" attr.type="double"/>"#)?;
+    writeln!(file, r#"  <graph id="MovieGraph" edgedefault="undirected">"#)?;
+
+    for (node, movie) in graph.node_references() {
+        writeln!(file, r#"    <node id="n{}">"#, node.index())?;
+        writeln!(file, r#"      <data key="title">{}</data>"#, escape_xml(&movie.title))?;
+        writeln!(file, r#"      <data key="year">{}</data>"#, movie.year)?;
+        writeln!(file, r#"      <data key="genres">{}</data>"#, escape_xml(&movie.genres.join(";")))?;
+        writeln!(file, "    </node>")?;
+    }
+
+    for edge in graph.edge_references() {
+        writeln!(
+            file,
+            r#"    <edge source="n{}" target="n{}">"#,
+            edge.source().index(),
+            edge.target().index()
+        )?;
+        writeln!(file, r#"      <data key="weight">{}</data>"#, edge.weight())?;
+        writeln!(file, "    </edge>")?;
+    }
+
+    writeln!(file, "  </graph>")?;
+    writeln!(file, "</graphml>")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_cleaning::CleanMovie;
+
+    fn sample_graph() -> Graph<MovieNode, f64, Undirected> {
+        let mut graph = Graph::<MovieNode, f64, Undirected>::new_undirected();
+        let clean = |title: &str| CleanMovie {
+            title: title.into(),
+            year: 2000,
+            genres: vec!["Drama".into()],
+            critic_score: 5.0,
+            user_score: 5.0,
+            score_gap: 0.0,
+        };
+        let to_node = |c: CleanMovie| MovieNode {
+            title: c.title,
+            year: c.year,
+            genres: c.genres,
+            critic_score: c.critic_score,
+            user_score: c.user_score,
+        };
+        let a = graph.add_node(to_node(clean("A")));
+        let b = graph.add_node(to_node(clean("B")));
+        graph.add_edge(a, b, 0.8);
+        graph
+    }
+
+    #[test]
+    fn test_write_dot_and_graphml_roundtrip_to_disk() {
+        let graph = sample_graph();
+        let node_indices: Vec<NodeIndex> = graph.node_indices().collect();
+        let clusters = vec![node_indices];
+        let cluster_of = cluster_id_lookup(&clusters);
+
+        let dot_path = std::env::temp_dir().join("imdb_graph_analysis_test.dot");
+        let graphml_path = std::env::temp_dir().join("imdb_graph_analysis_test.graphml");
+
+        write_dot(dot_path.to_str().unwrap(), &graph, &cluster_of).unwrap();
+        write_graphml(graphml_path.to_str().unwrap(), &graph).unwrap();
+
+        let dot_contents = std::fs::read_to_string(&dot_path).unwrap();
+        assert!(dot_contents.contains("graph MovieGraph"));
+        assert!(dot_contents.contains("cluster=0"));
+
+        let graphml_contents = std::fs::read_to_string(&graphml_path).unwrap();
+        assert!(graphml_contents.contains("<graphml"));
+        assert!(graphml_contents.contains("weight"));
+
+        std::fs::remove_file(dot_path).ok();
+        std::fs::remove_file(graphml_path).ok();
+    }
+
+    // two distinct nodes sharing the same title (e.g. a remake) must be able
+    // to land in different clusters and get different colors, which isn't
+    // possible if cluster_of is keyed by title instead of NodeIndex.
+    #[test]
+    fn test_cluster_id_lookup_distinguishes_same_titled_nodes() {
+        let mut graph = Graph::<MovieNode, f64, Undirected>::new_undirected();
+        let clean = |title: &str, year: u16| CleanMovie {
+            title: title.into(),
+            year,
+            genres: vec!["Drama".into()],
+            critic_score: 5.0,
+            user_score: 5.0,
+            score_gap: 0.0,
+        };
+        let to_node = |c: CleanMovie| MovieNode {
+            title: c.title,
+            year: c.year,
+            genres: c.genres,
+            critic_score: c.critic_score,
+            user_score: c.user_score,
+        };
+        // "Same Title" released twice, in unrelated parts of the graph
+        let original = graph.add_node(to_node(clean("Same Title", 1975)));
+        let remake = graph.add_node(to_node(clean("Same Title", 2020)));
+
+        let clusters = vec![vec![original], vec![remake]];
+        let cluster_of = cluster_id_lookup(&clusters);
+
+        assert_eq!(cluster_of.get(&original), Some(&0));
+        assert_eq!(cluster_of.get(&remake), Some(&1));
+    }
+}
@@ -0,0 +1,175 @@
+// K-Means
+// Purpose: I want a feature-vector clustering path that's independent of
+// the similarity graph, so users can compare structural clusters
+// (find_clusters / find_clusters_threshold) against clusters driven purely
+// by each movie's score/genre profile.
+
+use crate::data_cleaning::CleanMovie;
+use rand::Rng;
+use std::collections::HashSet;
+
+// Computing Euclidean distance between two equal-length feature vectors.
+fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt()
+}
+
+// Building a normalized feature vector per movie: critic_score and
+// user_score scaled to 0-1, the raw score_gap, then a multi-hot encoding
+// over the global genre vocabulary (1.0 if the movie has that genre, else 0.0).
+// The argument is movies, the cleaned movie data.
+// This will return the feature vectors alongside the genre vocabulary they
+// were built against, in the same order the multi-hot columns appear.
+fn build_feature_vectors(movies: &[CleanMovie]) -> (Vec<Vec<f64>>, Vec<String>) {
+    let mut vocab = Vec::new();
+    let mut seen = HashSet::new();
+    for movie in movies {
+        for genre in &movie.genres {
+            if seen.insert(genre.clone()) {
+                vocab.push(genre.clone());
+            }
+        }
+    }
+
+    let vectors = movies
+        .iter()
+        .map(|movie| {
+            let mut v = vec![movie.critic_score / 10.0, movie.user_score / 10.0, movie.score_gap];
+            for genre in &vocab {
+                v.push(if movie.genres.contains(genre) { 1.0 } else { 0.0 });
+            }
+            v
+        })
+        .collect();
+
+    (vectors, vocab)
+}
+
+// Seeding k initial centroids with k-means++: the first centroid is chosen
+// uniformly at random, then each subsequent centroid is chosen with
+// probability proportional to its squared distance from the nearest
+// already-chosen centroid, so the seeds start out spread across the data.
+fn kmeans_plus_plus_seed(vectors: &[Vec<f64>], k: usize, rng: &mut impl Rng) -> Vec<Vec<f64>> {
+    let mut centroids = Vec::with_capacity(k);
+    centroids.push(vectors[rng.gen_range(0..vectors.len())].clone());
+
+    while centroids.len() < k {
+        let weights: Vec<f64> = vectors
+            .iter()
+            .map(|v| {
+                centroids
+                    .iter()
+                    .map(|c| euclidean_distance(v, c).powi(2))
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        if total == 0.0 {
+            // every point coincides with an already-chosen centroid
+            centroids.push(vectors[rng.gen_range(0..vectors.len())].clone());
+            continue;
+        }
+
+        let mut target = rng.gen_range(0.0..total);
+        let mut chosen = weights.len() - 1;
+        for (i, w) in weights.iter().enumerate() {
+            if target < *w {
+                chosen = i;
+                break;
+            }
+            target -= w;
+        }
+        centroids.push(vectors[chosen].clone());
+    }
+
+    centroids
+}
+
+// Running Lloyd's k-means on the movies' feature vectors.
+// The arguments are movies, the cleaned movie data; k, the number of
+// clusters to form; and max_iters, the cap on assignment/update iterations.
+// This will return a vector of clusters, each a list of movie titles.
+// To do so, it seeds k centroids with k-means++, then repeatedly assigns
+// each movie to its nearest centroid by Euclidean distance and recomputes
+// centroids as the per-cluster mean, stopping early once no assignment changes.
+pub fn kmeans_clusters(movies: &[CleanMovie], k: usize, max_iters: usize) -> Vec<Vec<String>> {
+    if movies.is_empty() || k == 0 {
+        return Vec::new();
+    }
+    let (vectors, _vocab) = build_feature_vectors(movies);
+    let k = k.min(vectors.len());
+    let dim = vectors[0].len();
+
+    let mut rng = rand::thread_rng();
+    let mut centroids = kmeans_plus_plus_seed(&vectors, k, &mut rng);
+    let mut assignments = vec![0usize; vectors.len()];
+
+    for _ in 0..max_iters {
+        let mut changed = false;
+
+        // assigning each movie to its nearest centroid
+        for (i, v) in vectors.iter().enumerate() {
+            let (best, _) = centroids
+                .iter()
+                .enumerate()
+                .map(|(c_idx, c)| (c_idx, euclidean_distance(v, c)))
+                .fold((0, f64::INFINITY), |best, cur| if cur.1 < best.1 { cur } else { best });
+            if assignments[i] != best {
+                assignments[i] = best;
+                changed = true;
+            }
+        }
+
+        // recomputing centroids as the per-cluster mean
+        let mut sums = vec![vec![0.0; dim]; k];
+        let mut counts = vec![0usize; k];
+        for (i, v) in vectors.iter().enumerate() {
+            let c = assignments[i];
+            counts[c] += 1;
+            for d in 0..dim {
+                sums[c][d] += v[d];
+            }
+        }
+        for c in 0..k {
+            if counts[c] > 0 {
+                for value in sums[c].iter_mut() {
+                    *value /= counts[c] as f64;
+                }
+                centroids[c] = sums[c].clone();
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let mut clusters = vec![Vec::new(); k];
+    for (i, movie) in movies.iter().enumerate() {
+        clusters[assignments[i]].push(movie.title.clone());
+    }
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kmeans_clusters_separates_distinct_groups() {
+        let sample = vec![
+            CleanMovie { title: "A1".into(), year: 1994, genres: vec!["Drama".into()], critic_score: 9.0, user_score: 9.0, score_gap: 0.0 },
+            CleanMovie { title: "A2".into(), year: 1995, genres: vec!["Drama".into()], critic_score: 9.2, user_score: 8.8, score_gap: -0.4 },
+            CleanMovie { title: "B1".into(), year: 2001, genres: vec!["Comedy".into()], critic_score: 2.0, user_score: 2.0, score_gap: 0.0 },
+            CleanMovie { title: "B2".into(), year: 2002, genres: vec!["Comedy".into()], critic_score: 2.1, user_score: 1.9, score_gap: -0.2 },
+        ];
+        let clusters = kmeans_clusters(&sample, 2, 50);
+        assert_eq!(clusters.len(), 2);
+        let total: usize = clusters.iter().map(|c| c.len()).sum();
+        assert_eq!(total, sample.len());
+        // the high-score and low-score movies should land in different clusters
+        let a_cluster = clusters.iter().position(|c| c.contains(&"A1".to_string())).unwrap();
+        let b_cluster = clusters.iter().position(|c| c.contains(&"B1".to_string())).unwrap();
+        assert_ne!(a_cluster, b_cluster);
+    }
+}
@@ -5,20 +5,36 @@
 mod data_cleaning;
 mod graph_builder;
 mod analysis;
+mod stats;
+mod kmeans;
+mod graph_export;
 
 use std::error::Error;
 use std::env;
 use std::fs::File;
 use std::io::Write;
 use data_cleaning::load_and_clean_data;
-use graph_builder::build_graph;
+use graph_builder::{build_graph, build_graph_with_cores};
 use analysis::*;
+use stats::{score_gap_stats_by_decade, score_gap_stats_by_genre, GroupStats};
+use kmeans::kmeans_clusters;
+use graph_export::{cluster_id_lookup, write_dot, write_graphml};
+
+// defaults for the feature-vector k-means path, which isn't wired up to CLI args
+const KMEANS_K: usize = 8;
+const KMEANS_MAX_ITERS: usize = 100;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    // I want to parse the args: [binary] [csv_path] [genre_weight] [score_weight] [similarity_threshold]
+    // I want to parse the args: [binary] [csv_path] [genre_weight] [score_weight] [similarity_threshold] [num_cores] [cluster_cutoff] [fast_betweenness]
+    // num_cores is optional; when given, the similarity pass is split across that many threads.
+    // cluster_cutoff is optional and only read when num_cores is also given (to keep the
+    // args positional); it's the tighter union-find clustering threshold, distinct from the
+    // graph-edge similarity_threshold. Defaults to 1.5x the similarity_threshold.
+    // fast_betweenness is optional and only read when cluster_cutoff is also given; pass 1 to
+    // use the unit-weight BFS fast mode for betweenness centrality instead of weighted Dijkstra.
     let args: Vec<String> = env::args().collect();
-    if args.len() != 5 {
-        eprintln!("Usage: {} <csv_path> <genre_weight> <score_weight> <similarity_threshold>", args[0]);
+    if args.len() != 5 && args.len() != 6 && args.len() != 7 && args.len() != 8 {
+        eprintln!("Usage: {} <csv_path> <genre_weight> <score_weight> <similarity_threshold> [num_cores] [cluster_cutoff] [fast_betweenness]", args[0]);
         std::process::exit(1);
     }
     // Extracting arguments and converting types
@@ -26,20 +42,34 @@ fn main() -> Result<(), Box<dyn Error>> {
     let genre_weight: f64 = args[2].parse()?;
     let score_weight: f64 = args[3].parse()?;
     let threshold: f64 = args[4].parse()?;
+    let num_cores: Option<usize> = if args.len() >= 6 { Some(args[5].parse()?) } else { None };
+    let cluster_cutoff: f64 = if args.len() >= 7 { args[6].parse()? } else { threshold * 1.5 };
+    let fast_betweenness: bool = if args.len() == 8 { args[7].parse::<u8>()? != 0 } else { false };
 
     println!("Loading and cleaning data...");
     let movies = load_and_clean_data(csv_path)?;
     println!("{} movies loaded.", movies.len());
 
     println!("Building graph...");
-    let graph = build_graph(&movies, genre_weight, score_weight, threshold);
+    let graph = match num_cores {
+        Some(cores) => {
+            println!("Splitting similarity computation across {} cores.", cores);
+            build_graph_with_cores(&movies, genre_weight, score_weight, threshold, cores)
+        }
+        None => build_graph(&movies, genre_weight, score_weight, threshold),
+    };
     println!("Graph built with {} nodes and {} edges.", graph.node_count(), graph.edge_count());
 
     println!("Running analysis...");
 
     // Centrality metrics
     let degree = degree_centrality(&graph);
-    let between = betweenness_centrality(&graph);
+    let between = if fast_betweenness {
+        println!("Using unit-weight BFS fast mode for betweenness centrality.");
+        betweenness_centrality_fast(&graph)
+    } else {
+        betweenness_centrality(&graph)
+    };
 
     // score gap trends
     let by_decade = average_score_gap_by_decade(&movies);
@@ -48,18 +78,38 @@ fn main() -> Result<(), Box<dyn Error>> {
     
     let by_genre = average_score_gap_by_genre(&movies);
 
+    // richer per-group stats: std dev, confidence interval, and critic/user correlation
+    let decade_stats = score_gap_stats_by_decade(&movies);
+    let decade_stats_str: std::collections::HashMap<String, GroupStats> =
+        decade_stats.iter().map(|(k, v)| (k.to_string(), v.clone())).collect();
+    let genre_stats = score_gap_stats_by_genre(&movies);
+
     //finding clusters of related films in the graph
     let clusters = find_clusters(&graph);
+    //also finding the tighter union-find threshold clusters
+    let threshold_clusters = find_clusters_threshold(&graph, cluster_cutoff);
+    //and a feature-vector clustering path, independent of the similarity graph
+    let kmeans_result = kmeans_clusters(&movies, KMEANS_K, KMEANS_MAX_ITERS);
 
     println!("Writing output to 'output/' directory...");
     std::fs::create_dir_all("output")?;
-    
+
     //saving analysis results to the csv and txt files
     write_csv("output/degree_centrality.csv", &degree)?;
     write_csv("output/betweenness_centrality.csv", &between)?;
     write_csv("output/score_gap_by_decade.csv", &by_decade_str)?;
     write_csv("output/score_gap_by_genre.csv", &by_genre)?;
     write_clusters("output/movie_clusters.txt", &clusters)?;
+    write_clusters("output/movie_clusters_threshold.txt", &threshold_clusters)?;
+    write_clusters("output/movie_clusters_kmeans.txt", &kmeans_result)?;
+
+    // exporting the similarity graph itself for external visualization, colored by component cluster
+    let cluster_nodes = find_clusters_by_node(&graph);
+    let cluster_of = cluster_id_lookup(&cluster_nodes);
+    write_dot("output/movie_graph.dot", &graph, &cluster_of)?;
+    write_graphml("output/movie_graph.graphml", &graph)?;
+    write_stats_csv("output/score_gap_stats_by_decade.csv", &decade_stats_str)?;
+    write_stats_csv("output/score_gap_stats_by_genre.csv", &genre_stats)?;
 
     println!("Analysis complete. Outputs saved.");
     Ok(())
@@ -78,6 +128,24 @@ fn write_csv<T: std::fmt::Display>(path: &str, data: &std::collections::HashMap<
     Ok(())
 }
 
+// Writing a HashMap<K, GroupStats> out to a CSV file with one row per group.
+// The arguments are path which is the outputting file path
+// and data where the HashMap has every key being a group label (decade or genre)
+// and the value is that group's GroupStats.
+// The output will have a csv with columns group,mean,std_dev,n,ci_low,ci_high,correlation.
+fn write_stats_csv<K: std::fmt::Display>(path: &str, data: &std::collections::HashMap<K, GroupStats>) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(path)?;
+    writeln!(file, "group,mean,std_dev,n,ci_low,ci_high,correlation")?;
+    for (group, stats) in data {
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{}",
+            group, stats.mean, stats.std_dev, stats.n, stats.ci_low, stats.ci_high, stats.correlation
+        )?;
+    }
+    Ok(())
+}
+
 // Writing each movie clusters to a plain text file, each cluster on its own line.
 // the parameters are path which is the ouput txt file
 // and clusters which is the vector of clusters that are each a vector of the movie titles
@@ -0,0 +1,187 @@
+// Stats
+// Purpose: I want to go beyond a bare mean score gap per decade/genre,
+// which hides dispersion and can be misleading for small groups.
+// This module computes the sample standard deviation, a 95% confidence
+// interval, and the critic/user score correlation alongside each mean.
+
+use crate::data_cleaning::CleanMovie;
+use std::collections::HashMap;
+
+// The z-score for a 95% confidence interval under the normal approximation.
+const Z_95: f64 = 1.96;
+
+// Summary statistics for one group (a decade or a genre) of movies.
+// Fields:
+// mean: the average score_gap within the group
+// std_dev: the sample standard deviation of score_gap within the group
+// n: how many movies are in the group
+// ci_low / ci_high: the 95% confidence interval around mean, using the
+//   normal approximation mean ± z*std_dev/sqrt(n)
+// correlation: the Pearson correlation coefficient between critic_score
+//   and user_score within the group
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupStats {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub n: usize,
+    pub ci_low: f64,
+    pub ci_high: f64,
+    pub correlation: f64,
+}
+
+// Computing the sample standard deviation of a slice of values.
+// The argument is values, a slice of f64, and mean, its already-computed average.
+// This will return 0.0 when there's fewer than two values, since sample
+// variance is undefined for n <= 1.
+fn sample_std_dev(values: &[f64], mean: f64) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    variance.sqrt()
+}
+
+// Computing the Pearson correlation coefficient between two equal-length slices.
+// The arguments are xs and ys, the paired samples (critic_score and user_score here).
+// This will return 0.0 when there are fewer than two points or either
+// series has zero variance, since correlation is undefined there.
+fn pearson_correlation(xs: &[f64], ys: &[f64]) -> f64 {
+    let n = xs.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let mean_x = xs.iter().sum::<f64>() / n as f64;
+    let mean_y = ys.iter().sum::<f64>() / n as f64;
+
+    let mut cov = 0.0;
+    let mut var_x = 0.0;
+    let mut var_y = 0.0;
+    for i in 0..n {
+        let dx = xs[i] - mean_x;
+        let dy = ys[i] - mean_y;
+        cov += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+
+    if var_x == 0.0 || var_y == 0.0 {
+        0.0
+    } else {
+        cov / (var_x.sqrt() * var_y.sqrt())
+    }
+}
+
+// Building a GroupStats from the raw score_gap, critic_score and user_score
+// samples for one group. The arguments are gaps, critics and users, parallel
+// slices (same index = same movie) collected for a single decade or genre.
+// This will return the mean, sample standard deviation, n, 95% confidence
+// interval (normal approximation) and Pearson correlation for the group.
+fn summarize_group(gaps: &[f64], critics: &[f64], users: &[f64]) -> GroupStats {
+    let n = gaps.len();
+    let mean = gaps.iter().sum::<f64>() / n as f64;
+    let std_dev = sample_std_dev(gaps, mean);
+    let margin = if n > 0 { Z_95 * std_dev / (n as f64).sqrt() } else { 0.0 };
+    let correlation = pearson_correlation(critics, users);
+
+    GroupStats {
+        mean,
+        std_dev,
+        n,
+        ci_low: mean - margin,
+        ci_high: mean + margin,
+        correlation,
+    }
+}
+
+// Computing score_gap statistics grouped by decade.
+// The argument is movies, the cleaned movie data.
+// This will return a HashMap from decade to its GroupStats.
+// To do so, it groups each movie's score_gap, critic_score and user_score
+// by decade (year normalized the same way as average_score_gap_by_decade),
+// then summarizes each group.
+pub fn score_gap_stats_by_decade(movies: &[CleanMovie]) -> HashMap<u16, GroupStats> {
+    let mut gaps: HashMap<u16, Vec<f64>> = HashMap::new();
+    let mut critics: HashMap<u16, Vec<f64>> = HashMap::new();
+    let mut users: HashMap<u16, Vec<f64>> = HashMap::new();
+
+    for movie in movies {
+        let decade = (movie.year / 10) * 10;
+        gaps.entry(decade).or_default().push(movie.score_gap);
+        critics.entry(decade).or_default().push(movie.critic_score);
+        users.entry(decade).or_default().push(movie.user_score);
+    }
+
+    gaps.into_iter()
+        .map(|(decade, group_gaps)| {
+            let stats = summarize_group(&group_gaps, &critics[&decade], &users[&decade]);
+            (decade, stats)
+        })
+        .collect()
+}
+
+// Computing score_gap statistics grouped by genre.
+// The argument is movies, the cleaned movie data.
+// This will return a HashMap from genre name to its GroupStats.
+// To do so, it groups each movie's score_gap, critic_score and user_score
+// by every genre it's tagged with (same membership as average_score_gap_by_genre),
+// then summarizes each group.
+pub fn score_gap_stats_by_genre(movies: &[CleanMovie]) -> HashMap<String, GroupStats> {
+    let mut gaps: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut critics: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut users: HashMap<String, Vec<f64>> = HashMap::new();
+
+    for movie in movies {
+        for genre in &movie.genres {
+            gaps.entry(genre.clone()).or_default().push(movie.score_gap);
+            critics.entry(genre.clone()).or_default().push(movie.critic_score);
+            users.entry(genre.clone()).or_default().push(movie.user_score);
+        }
+    }
+
+    gaps.into_iter()
+        .map(|(genre, group_gaps)| {
+            let stats = summarize_group(&group_gaps, &critics[&genre], &users[&genre]);
+            (genre, stats)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_gap_stats_by_genre() {
+        let sample = vec![
+            CleanMovie {
+                title: "Test".into(),
+                year: 1994,
+                genres: vec!["Drama".into()],
+                critic_score: 7.5,
+                user_score: 8.2,
+                score_gap: 0.7,
+            },
+            CleanMovie {
+                title: "Test2".into(),
+                year: 1999,
+                genres: vec!["Drama".into()],
+                critic_score: 6.5,
+                user_score: 7.5,
+                score_gap: 1.0,
+            },
+        ];
+        let result = score_gap_stats_by_genre(&sample);
+        let drama = result.get("Drama").expect("Drama group should exist");
+        assert_eq!(drama.n, 2);
+        assert!((drama.mean - 0.85).abs() < 1e-9);
+        assert!(drama.std_dev > 0.0);
+        assert!(drama.ci_low < drama.mean && drama.mean < drama.ci_high);
+    }
+
+    #[test]
+    fn test_pearson_correlation_perfect_positive() {
+        let xs = vec![1.0, 2.0, 3.0, 4.0];
+        let ys = vec![2.0, 4.0, 6.0, 8.0];
+        assert!((pearson_correlation(&xs, &ys) - 1.0).abs() < 1e-9);
+    }
+}